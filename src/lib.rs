@@ -12,7 +12,19 @@
 //!
 //! Next, add a `WindowManager` to your app's state. It keeps track of all of the `Id`s and corresponding `Window`s that are currently open. It also provides `view`, `theme`, and `title` methods that return the proper output for the specified `Id`.
 //!
-//! You have to manually inform the `WindowManager` when a window is closed. This can be done by subscribing to `iced::window::close_events()` and passing the `Id` of each closed window to `WindowManager::was_closed()`.
+//! You have to manually inform the `WindowManager` when a window is closed. This can be done by subscribing to `iced::window::close_events()` and passing the `Id` of each closed window, along with a mutable reference to your app, to `WindowManager::was_closed()`. The `Task<Message>` it returns should be batched into your `update`'s returned task.
+//!
+//! Windows can react to their own lifecycle by implementing `Window::on_open` and `Window::on_close`, which are invoked by `WindowManager::open` and `WindowManager::was_closed` respectively. This is a convenient place to kick off data fetching when a window appears or to flush state when it is destroyed, without having to scatter that logic through your app's central `update`.
+//!
+//! If you'd like your app to exit once its last window is closed, call `WindowManager::set_exit_on_last_close(true)`. Windows that shouldn't keep the app running on their own, such as tray or status windows, can opt out by overriding `Window::counts_toward_exit`.
+//!
+//! For windows that should only ever have a single instance open at a time, such as a "Settings" or "About" window, use `WindowManager::open_unique` instead of `WindowManager::open`. If an instance is already open, it's focused instead of a duplicate being spawned.
+//!
+//! `WindowManager` can also track which window currently holds focus. Subscribe to `iced::window::events()` and pass the `Id` of each `window::Event::Focused` event to `WindowManager::notify_focus_changed()`, then query the focused window with `WindowManager::focused()` or `WindowManager::focused_title()`.
+//!
+//! To persist which windows are open across restarts, call `WindowManager::snapshot()` to capture a `Vec<WindowDescriptor>` that can be written to disk, and `WindowManager::restore()` to reopen them later, mapping each descriptor back to a concrete window with a factory closure you provide. Enable the `serde` feature to make `WindowDescriptor` serializable.
+//!
+//! `WindowManager::close_instances_of()` and `WindowManager::update_each()` let you act on every open instance of a window type, or every open window, in one call instead of manually iterating `instances_of` and batching tasks yourself.
 
 use iced::{
     window::{self, Id},
@@ -32,6 +44,32 @@ pub trait Window<App, Theme, Message, Renderer = iced::Renderer>:
     fn eq(&self, other: &dyn Window<App, Theme, Message, Renderer>) -> bool {
         self.id() == other.id()
     }
+
+    /// Called when this window is opened, after it has been assigned an `Id`. The returned task
+    /// is chained into the task returned by `WindowManager::open`, running once the window has
+    /// been created.
+    ///
+    /// Defaults to doing nothing.
+    fn on_open(&self, _id: Id, _app: &mut App) -> Task<Message> {
+        Task::none()
+    }
+
+    /// Called when this window is closed, just after it is removed from the `WindowManager`. The
+    /// returned task is batched into the task returned by `WindowManager::was_closed`.
+    ///
+    /// Defaults to doing nothing.
+    fn on_close(&self, _id: Id, _app: &mut App) -> Task<Message> {
+        Task::none()
+    }
+
+    /// Whether this window should be counted when `WindowManager` decides if all windows have
+    /// been closed for its `exit_on_last_close` behavior.
+    ///
+    /// Defaults to `true`. Override this to return `false` for headless or tray/status windows
+    /// that shouldn't keep the app alive on their own.
+    fn counts_toward_exit(&self) -> bool {
+        true
+    }
 }
 
 trait WindowClone<App, Theme, Message, Renderer> {
@@ -52,8 +90,48 @@ impl<App, Theme, Message, Renderer, T: 'static + Window<App, Theme, Message, Ren
     }
 }
 
+/// The position and size of an open window, captured as part of a `WindowDescriptor`.
+///
+/// `iced::window::Settings` can't be captured wholesale for this purpose since it carries
+/// platform-specific and icon data that isn't serializable, and `iced`'s own geometry types
+/// (`Size`, `Point`) aren't serializable either, so only plain numeric fields are kept here.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct WindowGeometry {
+    pub width: f32,
+    pub height: f32,
+    pub position: Option<(f32, f32)>,
+}
+
+impl From<&window::Settings> for WindowGeometry {
+    fn from(settings: &window::Settings) -> Self {
+        Self {
+            width: settings.size.width,
+            height: settings.size.height,
+            position: match settings.position {
+                window::Position::Specific(point) => Some((point.x, point.y)),
+                _ => None,
+            },
+        }
+    }
+}
+
+/// A serializable snapshot of an open window, captured by `WindowManager::snapshot` and consumed
+/// by `WindowManager::restore` to reopen the window across a restart.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct WindowDescriptor {
+    /// The `Window::id` of the window this descriptor was captured from.
+    pub id: String,
+    /// The position and size from the window's `Window::settings` at the time it was captured.
+    /// Moving or resizing the window after it has opened is not reflected here.
+    pub geometry: WindowGeometry,
+}
+
 pub struct WindowManager<App, Theme, Message, Renderer = iced::Renderer> {
     windows: HashMap<Id, Box<dyn Window<App, Theme, Message, Renderer>>>,
+    exit_on_last_close: bool,
+    focused: Option<Id>,
 }
 
 impl<App, Theme, Message, Renderer> WindowManager<App, Theme, Message, Renderer> {
@@ -80,12 +158,38 @@ impl<App, Theme, Message, Renderer> WindowManager<App, Theme, Message, Renderer>
     pub fn open(
         &mut self,
         window: Box<dyn Window<App, Theme, Message, Renderer>>,
-    ) -> (Id, Task<Id>) {
-        let (id, task) = window::open(window.settings());
+        app: &mut App,
+    ) -> (Id, Task<Message>)
+    where
+        Message: Send + 'static,
+    {
+        let (id, open_task) = window::open(window.settings());
+        let on_open_task = window.on_open(id, app);
         self.windows.insert(id, window);
+        // `on_open_task` may target the window itself (e.g. `window::gain_focus`), so it must
+        // not run until `open_task` has actually finished creating it.
+        let mut on_open_task = Some(on_open_task);
+        let task = open_task.then(move |_| on_open_task.take().unwrap_or_else(Task::none));
         (id, task)
     }
 
+    /// Opens the given window unless an instance of it is already open, in which case the
+    /// existing instance is focused instead of spawning a duplicate.
+    pub fn open_unique(
+        &mut self,
+        window: Box<dyn Window<App, Theme, Message, Renderer>>,
+        app: &mut App,
+    ) -> (Id, Task<Message>)
+    where
+        Message: Send + 'static,
+    {
+        if let Some((&id, _)) = self.instances_of(window.as_ref()).first() {
+            return (id, window::gain_focus::<Message>(id));
+        }
+
+        self.open(window, app)
+    }
+
     pub fn close_all(&mut self) -> Task<Id> {
         let mut tasks = Vec::new();
         for id in self.windows.keys() {
@@ -94,14 +198,68 @@ impl<App, Theme, Message, Renderer> WindowManager<App, Theme, Message, Renderer>
         Task::batch(tasks)
     }
 
+    /// Closes every open instance of the given window.
+    pub fn close_instances_of(
+        &mut self,
+        window: &dyn Window<App, Theme, Message, Renderer>,
+    ) -> Task<Id> {
+        let tasks = self
+            .instances_of(window)
+            .into_iter()
+            .map(|(&id, _)| window::close(id))
+            .collect::<Vec<_>>();
+        Task::batch(tasks)
+    }
+
+    /// Calls `f` with the Id and Window of every open window.
+    pub fn update_each(
+        &self,
+        mut f: impl FnMut(&Id, &Box<dyn Window<App, Theme, Message, Renderer>>),
+    ) {
+        for (id, window) in &self.windows {
+            f(id, window);
+        }
+    }
+
     /// Checks for any open instances of the given window.
     pub fn any_of(&self, window: &dyn Window<App, Theme, Message, Renderer>) -> bool {
         self.windows.values().any(|w| w.eq(window))
     }
 
-    /// Updates internal state to reflect that the given window Id  was closed.
-    pub fn was_closed(&mut self, id: Id) {
-        self.windows.remove(&id);
+    /// Updates internal state to reflect that the given window Id was closed, running that
+    /// window's `on_close` hook and returning the task it produces.
+    ///
+    /// If `exit_on_last_close` is set and no remaining window counts toward exit (see
+    /// `Window::counts_toward_exit`), the returned task is also batched with an app-exit task.
+    pub fn was_closed(&mut self, id: Id, app: &mut App) -> Task<Message>
+    where
+        Message: Send + 'static,
+    {
+        let Some(window) = self.windows.remove(&id) else {
+            return Task::none();
+        };
+        if self.focused == Some(id) {
+            self.focused = None;
+        }
+        let on_close_task = window.on_close(id, app);
+
+        if self.should_exit() {
+            Task::batch([on_close_task, iced::exit()])
+        } else {
+            on_close_task
+        }
+    }
+
+    /// Whether the app should exit given the current set of open windows and the
+    /// `exit_on_last_close` setting.
+    fn should_exit(&self) -> bool {
+        self.exit_on_last_close && !self.windows.values().any(|w| w.counts_toward_exit())
+    }
+
+    /// Sets whether the app should exit once the last window counting toward exit is closed (see
+    /// `Window::counts_toward_exit`). Defaults to `false`.
+    pub fn set_exit_on_last_close(&mut self, exit_on_last_close: bool) {
+        self.exit_on_last_close = exit_on_last_close;
     }
 
     /// Returns all instances of the given window and their associated Ids.
@@ -116,12 +274,174 @@ impl<App, Theme, Message, Renderer> WindowManager<App, Theme, Message, Renderer>
     pub fn empty(&self) -> bool {
         self.windows.is_empty()
     }
+
+    /// Updates internal state to reflect that the given window Id gained focus. Wire this up from
+    /// `iced::window::events()` filtered for `window::Event::Focused`, the same way `was_closed`
+    /// is fed from `iced::window::close_events`.
+    pub fn notify_focus_changed(&mut self, id: Id) {
+        self.focused = Some(id);
+    }
+
+    /// Returns the currently focused window and its Id, if any.
+    #[allow(clippy::type_complexity)]
+    pub fn focused(&self) -> Option<(&Id, &Box<dyn Window<App, Theme, Message, Renderer>>)> {
+        let id = self.focused.as_ref()?;
+        self.windows.get_key_value(id)
+    }
+
+    /// Convenience wrapper around `focused` that returns the title of the focused window, if any.
+    pub fn focused_title(&self, app: &App) -> Option<String> {
+        self.focused().map(|(_, window)| window.title(app))
+    }
+
+    /// Captures the `id()` and geometry of every open window, for later use with `restore`.
+    pub fn snapshot(&self) -> Vec<WindowDescriptor> {
+        self.windows
+            .values()
+            .map(|window| WindowDescriptor {
+                id: window.id().to_string(),
+                geometry: WindowGeometry::from(&window.settings()),
+            })
+            .collect()
+    }
+
+    /// Reopens a set of windows previously captured with `snapshot`. `factory` maps each
+    /// descriptor back to a concrete window, which is then opened the same way as `open`.
+    pub fn restore(
+        &mut self,
+        descriptors: Vec<WindowDescriptor>,
+        app: &mut App,
+        factory: impl Fn(&WindowDescriptor) -> Box<dyn Window<App, Theme, Message, Renderer>>,
+    ) -> Task<Message>
+    where
+        Message: Send + 'static,
+    {
+        let tasks = descriptors
+            .iter()
+            .map(|descriptor| self.open(factory(descriptor), app).1)
+            .collect::<Vec<_>>();
+        Task::batch(tasks)
+    }
 }
 
 impl<App, Theme, Message, Renderer> Default for WindowManager<App, Theme, Message, Renderer> {
     fn default() -> Self {
         Self {
             windows: HashMap::new(),
+            exit_on_last_close: false,
+            focused: None,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Clone)]
+    struct TestWindow {
+        id: &'static str,
+        counts_toward_exit: bool,
+    }
+
+    impl Window<(), iced::Theme, ()> for TestWindow {
+        fn view<'a>(&self, _app: &'a ()) -> Element<'a, (), iced::Theme, iced::Renderer> {
+            iced::widget::text("").into()
+        }
+
+        fn title(&self, _app: &()) -> String {
+            self.id.to_string()
+        }
+
+        fn theme(&self, _app: &()) -> iced::Theme {
+            iced::Theme::default()
+        }
+
+        fn settings(&self) -> window::Settings {
+            window::Settings::default()
+        }
+
+        fn id(&self) -> &'static str {
+            self.id
+        }
+
+        fn counts_toward_exit(&self) -> bool {
+            self.counts_toward_exit
+        }
+    }
+
+    fn manager() -> WindowManager<(), iced::Theme, ()> {
+        WindowManager::default()
+    }
+
+    #[test]
+    fn exits_when_last_counting_window_closes_even_with_non_counting_window_open() {
+        let mut app = ();
+        let mut windows = manager();
+        windows.set_exit_on_last_close(true);
+
+        let (counting_id, _) = windows.open(
+            Box::new(TestWindow {
+                id: "counting",
+                counts_toward_exit: true,
+            }),
+            &mut app,
+        );
+        let _ = windows.open(
+            Box::new(TestWindow {
+                id: "non-counting",
+                counts_toward_exit: false,
+            }),
+            &mut app,
+        );
+
+        let _ = windows.was_closed(counting_id, &mut app);
+
+        assert!(windows.should_exit());
+    }
+
+    #[test]
+    fn closing_a_non_focused_window_leaves_focus_untouched() {
+        let mut app = ();
+        let mut windows = manager();
+
+        let (focused_id, _) = windows.open(
+            Box::new(TestWindow {
+                id: "focused",
+                counts_toward_exit: true,
+            }),
+            &mut app,
+        );
+        let (other_id, _) = windows.open(
+            Box::new(TestWindow {
+                id: "other",
+                counts_toward_exit: true,
+            }),
+            &mut app,
+        );
+        windows.notify_focus_changed(focused_id);
+
+        let _ = windows.was_closed(other_id, &mut app);
+
+        assert_eq!(windows.focused().map(|(&id, _)| id), Some(focused_id));
+    }
+
+    #[test]
+    fn closing_the_focused_window_clears_focus() {
+        let mut app = ();
+        let mut windows = manager();
+
+        let (focused_id, _) = windows.open(
+            Box::new(TestWindow {
+                id: "focused",
+                counts_toward_exit: true,
+            }),
+            &mut app,
+        );
+        windows.notify_focus_changed(focused_id);
+
+        let _ = windows.was_closed(focused_id, &mut app);
+
+        assert!(windows.focused().is_none());
+    }
+}